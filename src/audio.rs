@@ -0,0 +1,122 @@
+// Optional sound subsystem for flappy dragon.
+//
+// The whole module is gated behind the `audio` Cargo feature so that
+// terminal-only and WASM builds — which have no output device — still
+// compile. When the feature is off, `Sounds` is a zero-sized stub whose
+// play methods do nothing, so the rest of the game can hold a `Sounds`
+// and call it unconditionally.
+
+#[cfg(feature = "audio")]
+mod imp {
+    use std::io::Cursor;
+
+    use rodio::buffer::SamplesBuffer;
+    use rodio::{OutputStream, OutputStreamHandle, Source};
+
+    // Short OGG clips loaded from the `assets/` directory at startup. They
+    // are read at runtime rather than embedded so the crate builds without
+    // the files present; a missing clip simply stays silent.
+    const FLAP_OGG: &str = "assets/flap.ogg";
+    const SCORE_OGG: &str = "assets/score.ogg";
+    const DEATH_OGG: &str = "assets/death.ogg";
+
+    // A clip decoded to interleaved PCM at load time, ready to replay.
+    struct Clip {
+        channels: u16,
+        sample_rate: u32,
+        samples: Vec<f32>,
+    }
+    impl Clip {
+        // Read and decode an OGG file into PCM using lewton. Returns None
+        // if the file is missing or can't be decoded so the caller can
+        // degrade silently.
+        fn decode(path: &str) -> Option<Self> {
+            let bytes = std::fs::read(path).ok()?;
+            let mut reader = lewton::inside_ogg::OggStreamReader::new(Cursor::new(bytes)).ok()?;
+            let channels = reader.ident_hdr.audio_channels as u16;
+            let sample_rate = reader.ident_hdr.audio_sample_rate;
+            let mut samples = Vec::new();
+            while let Ok(Some(packet)) = reader.read_dec_packet_itl() {
+                // lewton yields i16 samples; rodio wants f32 in [-1.0, 1.0].
+                samples.extend(packet.into_iter().map(|s| s as f32 / i16::MAX as f32));
+            }
+            Some(Clip {
+                channels,
+                sample_rate,
+                samples,
+            })
+        }
+        fn source(&self) -> impl Source<Item = f32> {
+            SamplesBuffer::new(self.channels, self.sample_rate, self.samples.clone())
+        }
+    }
+
+    // Holds the output stream for the game's lifetime plus the cached
+    // clips. The stream must be kept alive or playback stops immediately.
+    pub struct Sounds {
+        // Both are None on a headless/no-audio machine so the game still
+        // runs; the stream must be kept alive or playback stops.
+        _stream: Option<OutputStream>,
+        handle: Option<OutputStreamHandle>,
+        flap: Option<Clip>,
+        score: Option<Clip>,
+        death: Option<Clip>,
+    }
+    impl Sounds {
+        pub fn new() -> Self {
+            // If no output device is available, fall back to a silent stub
+            // with no stream or handle — never re-open the device.
+            match OutputStream::try_default() {
+                Ok((stream, handle)) => Sounds {
+                    _stream: Some(stream),
+                    handle: Some(handle),
+                    flap: Clip::decode(FLAP_OGG),
+                    score: Clip::decode(SCORE_OGG),
+                    death: Clip::decode(DEATH_OGG),
+                },
+                Err(_) => Sounds::silent(),
+            }
+        }
+        fn silent() -> Self {
+            Sounds {
+                _stream: None,
+                handle: None,
+                flap: None,
+                score: None,
+                death: None,
+            }
+        }
+        fn play(&self, clip: &Option<Clip>) {
+            if let (Some(handle), Some(clip)) = (&self.handle, clip) {
+                // Ignore playback errors so a missing device never crashes
+                // the game loop.
+                let _ = handle.play_raw(clip.source().convert_samples());
+            }
+        }
+        pub fn play_flap(&self) {
+            self.play(&self.flap);
+        }
+        pub fn play_score(&self) {
+            self.play(&self.score);
+        }
+        pub fn play_death(&self) {
+            self.play(&self.death);
+        }
+    }
+}
+
+#[cfg(not(feature = "audio"))]
+mod imp {
+    // Silent stub used when the `audio` feature is disabled.
+    pub struct Sounds;
+    impl Sounds {
+        pub fn new() -> Self {
+            Sounds
+        }
+        pub fn play_flap(&self) {}
+        pub fn play_score(&self) {}
+        pub fn play_death(&self) {}
+    }
+}
+
+pub use imp::Sounds;
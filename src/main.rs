@@ -1,16 +1,72 @@
+use std::collections::VecDeque; // a double-ended queue, used to hold the on-screen obstacles.
+
 use bracket_lib::prelude::*; // the * is a wildcard, it means use everything from bracket-lib
+
+mod audio;
+use audio::Sounds;
+
+mod profile;
+use profile::Scores;
                              // using the prelude is a rust convention that organises imports.
                              // It saves prefixing every call to the library with bracket-lib::prelude::.
 
 enum GameMode {
     Menu,
     Playing,
+    Paused,
     End,
 }
 
+// The difficulty tier selected on the main menu. It scales the tuning
+// constants — frame pace, gravity step and obstacle gaps — that were
+// previously hard-coded, and is threaded through restart and Obstacle::new
+// so the chosen setting survives replays.
+#[derive(Clone, Copy, PartialEq)]
+enum Difficulty {
+    Novice,
+    Normal,
+    Hard,
+}
+impl Difficulty {
+    // A longer frame duration makes Novice feel slower and Hard faster.
+    fn frame_duration(&self) -> f32 {
+        match self {
+            Difficulty::Novice => 90.0,
+            Difficulty::Normal => 75.0,
+            Difficulty::Hard => 60.0,
+        }
+    }
+    // A gentler gravity step gives Novice players more reaction time.
+    fn gravity_step(&self) -> f32 {
+        match self {
+            Difficulty::Novice => 0.15,
+            Difficulty::Normal => 0.2,
+            Difficulty::Hard => 0.25,
+        }
+    }
+    // The obstacle gap size for a given score. Novice keeps a larger
+    // minimum gap and shrinks it more slowly; Hard does the opposite.
+    fn obstacle_size(&self, score: i32) -> i32 {
+        match self {
+            Difficulty::Novice => i32::max(5, 20 - score / 2),
+            Difficulty::Normal => i32::max(2, 20 - score),
+            Difficulty::Hard => i32::max(2, 16 - score * 2),
+        }
+    }
+    fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Novice => "Novice",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+const NOVICE_THRESHOLD: i32 = 3; // below this on a first death, offer Novice mode.
+
 const SCREEN_WIDTH: i32 = 80;
 const SCREEN_HEIGHT: i32 = 50;
-const FRAME_DURATION: f32 = 75.0;
+const OBSTACLE_SPACING: i32 = 30; // horizontal distance between consecutive obstacles.
 
 struct Obstacle {
     x: i32,
@@ -18,12 +74,12 @@ struct Obstacle {
     size: i32,  // gap size.
 }
 impl Obstacle {
-    fn new(x: i32, score: i32) -> Self {
+    fn new(x: i32, score: i32, difficulty: Difficulty) -> Self {
         let mut random = RandomNumberGenerator::new();
         Obstacle {
             x,
             gap_y: random.range(10, 40), // gap location is random number between 10 and 39.
-            size: i32::max(2, 20 - score), // gaps get smaller as score gets larger, never less than 2.
+            size: difficulty.obstacle_size(score), // gaps shrink with score, scaled by difficulty.
         }
     }
     fn render(&mut self, ctx: &mut BTerm, player_x: i32) {
@@ -56,21 +112,21 @@ struct Player {
 }
 impl Player {
     fn new(x: i32, y: i32) -> Self {
-        return Self {
+        Self {
             x,
             y,
             velocity: 0.0, // floats must be fractional, 0 would cause a mis-matched type error.
-        };
+        }
     }
     fn render(&mut self, ctx: &mut BTerm) {
         // set is a bracket-lib function that sets a single char on the screen.
         // to_cp437 converts a unicode char from source code to the matching Codepage 437 char number.
         ctx.set(0, self.y, YELLOW, BLACK, to_cp437('@'))
     }
-    fn gravity_and_move(&mut self) {
+    fn gravity_and_move(&mut self, difficulty: Difficulty) {
         if self.velocity < 2.0 {
             // check for terminal velocity,
-            self.velocity += 0.2; // if not moving at terminal velocity, increase velocity
+            self.velocity += difficulty.gravity_step(); // scale the pull by difficulty.
         }
         // add the velocity to the players y, increasing y moves the player down.
         // to add a float to an int, the float must be converted to int.
@@ -90,19 +146,44 @@ impl Player {
 struct State {
     player: Player,
     frame_time: f32, // used to track the time between frames to control game speed.
-    obstacle: Obstacle,
+    obstacles: VecDeque<Obstacle>, // several pipes can be on screen at once.
     mode: GameMode,
     score: i32,
+    sounds: Sounds, // the optional sound subsystem, opened once in new().
+    scores: Scores, // the persistent high-score table, loaded once in new().
+    last_rank: Option<usize>, // index of this session's entry in scores, for highlighting.
+    difficulty: Difficulty, // the selected tier, chosen on the menu and kept across replays.
+    novice_offered: bool, // whether the one-time Novice prompt has been used this session.
+    show_novice: bool,    // whether dead() should currently show the Novice prompt.
 }
 impl State {
     fn new() -> Self {
         State {
             player: Player::new(5, 25),
             frame_time: 0.0,
-            obstacle: Obstacle::new(SCREEN_WIDTH, 0),
+            obstacles: State::new_obstacles(Difficulty::Normal),
             mode: GameMode::Menu,
             score: 0,
+            sounds: Sounds::new(),
+            scores: Scores::load(),
+            last_rank: None,
+            difficulty: Difficulty::Normal,
+            novice_offered: false,
+            show_novice: false,
+        }
+    }
+    // seed the queue with a few obstacles spaced across the screen so the
+    // game feels continuous rather than one pipe at a time.
+    fn new_obstacles(difficulty: Difficulty) -> VecDeque<Obstacle> {
+        let mut obstacles = VecDeque::new();
+        for i in 0..3 {
+            obstacles.push_back(Obstacle::new(
+                SCREEN_WIDTH + i * OBSTACLE_SPACING,
+                0,
+                difficulty,
+            ));
         }
+        obstacles
     }
     fn play(&mut self, ctx: &mut BTerm) {
         ctx.cls();
@@ -111,42 +192,113 @@ impl State {
                           // ctx.frame_time_ms contains the time elapsed since the last time tick() was called.
         self.frame_time += ctx.frame_time_ms;
         // self.frame_time counts up until FRAME_DURATION is reached
-        if self.frame_time > FRAME_DURATION {
+        if self.frame_time > self.difficulty.frame_duration() {
             self.frame_time = 0.0; // then it resets
-            self.player.gravity_and_move(); // and updates the game.
+            self.player.gravity_and_move(self.difficulty); // and updates the game.
         }
-        if let Some(VirtualKeyCode::Space) = ctx.key {
-            // if the user is pressing spacebar.
-            self.player.flap();
+        if let Some(key) = ctx.key {
+            match key {
+                // if the user is pressing spacebar, flap.
+                VirtualKeyCode::Space => {
+                    self.player.flap();
+                    self.sounds.play_flap();
+                }
+                // pressing P pauses the game mid-flight.
+                VirtualKeyCode::P => self.mode = GameMode::Paused,
+                _ => {}
+            }
         }
 
         self.player.render(ctx);
         ctx.print(0, 0, "Press SPACE to flap.");
 
         // display the current score.
-        ctx.print(0, 1, &format!("Score: {}", self.score));
+        ctx.print(0, 1, format!("Score: {}", self.score));
 
-        // render the obstacle.
-        self.obstacle.render(ctx, self.player.x);
+        // render every obstacle in the queue and test each for a collision.
+        let mut hit = false;
+        for obstacle in self.obstacles.iter_mut() {
+            obstacle.render(ctx, self.player.x);
+            if obstacle.hit_obstacle(&self.player) {
+                hit = true;
+            }
+        }
 
-        // if player passed obstacle, increment score and create a new obstacle.
-        if self.player.x > self.obstacle.x {
-            self.score += 1;
-            self.obstacle = Obstacle::new(self.player.x + SCREEN_WIDTH, self.score);
+        // if the player has passed the frontmost obstacle, pop it, score it,
+        // and push a fresh one a fixed spacing beyond the current rightmost.
+        if let Some(front) = self.obstacles.front() {
+            if self.player.x > front.x {
+                self.obstacles.pop_front();
+                self.score += 1;
+                self.sounds.play_score();
+                let last_x = self.obstacles.back().map_or(self.player.x, |o| o.x);
+                self.obstacles.push_back(Obstacle::new(
+                    last_x + OBSTACLE_SPACING,
+                    self.score,
+                    self.difficulty,
+                ));
+            }
         }
 
         // if flappy dragon falls off the bottom of the screen
-        // or flappy dragon his the obstacle, the game ends.
-        if self.player.y > SCREEN_HEIGHT || self.obstacle.hit_obstacle(&self.player) {
+        // or flappy dragon hits an obstacle, the game ends.
+        if self.player.y > SCREEN_HEIGHT || hit {
             self.mode = GameMode::End;
+            self.sounds.play_death();
+
+            // record the final score in the persistent table if it earns
+            // a spot, remembering its rank so dead() can highlight it.
+            if self.scores.qualifies(self.score) {
+                self.last_rank = Some(self.scores.insert(self.score));
+                self.scores.save();
+            }
+
+            // on the first sub-threshold death this session, offer a
+            // one-time switch to Novice mode, echoing classic novice-mode
+            // onboarding.
+            if !self.novice_offered
+                && self.difficulty != Difficulty::Novice
+                && self.score < NOVICE_THRESHOLD
+            {
+                self.show_novice = true;
+                self.novice_offered = true;
+            }
+        }
+    }
+    fn paused(&mut self, ctx: &mut BTerm) {
+        ctx.cls();
+        ctx.cls_bg(NAVY); // keep the same background as play.
+
+        // render the frozen frame. frame_time isn't accumulated and
+        // gravity_and_move() isn't called, so nothing moves while paused.
+        self.player.render(ctx);
+        ctx.print(0, 1, format!("Score: {}", self.score));
+        for obstacle in self.obstacles.iter_mut() {
+            obstacle.render(ctx, self.player.x);
+        }
+
+        // dim the frozen frame by laying a darkened overlay over every cell.
+        for y in 0..SCREEN_HEIGHT {
+            for x in 0..SCREEN_WIDTH {
+                ctx.set_bg(x, y, RGBA::from_f32(0.0, 0.0, 0.0, 0.5));
+            }
+        }
+
+        ctx.print_centered(SCREEN_HEIGHT / 2, "PAUSED — press P to resume");
+
+        // a second P press returns to Playing.
+        if let Some(VirtualKeyCode::P) = ctx.key {
+            self.mode = GameMode::Playing;
         }
     }
     fn restart(&mut self) {
         self.player = Player::new(5, 25);
         self.frame_time = 0.0;
-        self.obstacle = Obstacle::new(SCREEN_WIDTH, 0);
+        self.obstacles = State::new_obstacles(self.difficulty); // keep the chosen tier across replays.
         self.mode = GameMode::Playing;
         self.score = 0;
+        self.last_rank = None; // scores persists across restarts, but the highlight doesn't.
+        self.show_novice = false; // clear the prompt; novice_offered stays set for the session.
     }
     fn main_menu(&mut self, ctx: &mut BTerm) {
         ctx.cls();
@@ -154,6 +306,11 @@ impl State {
         ctx.print_centered(8, "(P) Play Game");
         ctx.print_centered(9, "(Q) Quit Game");
 
+        // difficulty selection via number keys; the current tier is shown.
+        ctx.print_centered(12, "Difficulty:");
+        ctx.print_centered(13, "(1) Novice  (2) Normal  (3) Hard");
+        ctx.print_centered(14, format!("Selected: {}", self.difficulty.label()));
+
         // Rust provides a shortened version of match for matching aginst a single case
         // named if let. Option is an enum of Some(data) and None.
         // Using match to work with Option would look like:
@@ -176,6 +333,9 @@ impl State {
             //if the user presses a key, extract the keys value
             // if let implies that if no key is pressed, do nothing.
             match key {
+                VirtualKeyCode::Key1 => self.difficulty = Difficulty::Novice,
+                VirtualKeyCode::Key2 => self.difficulty = Difficulty::Normal,
+                VirtualKeyCode::Key3 => self.difficulty = Difficulty::Hard,
                 VirtualKeyCode::P => self.restart(), // if P was pressed, restart the game.
                 VirtualKeyCode::Q => ctx.quitting = true, // if Q was pressed set quitting to true
                 _ => {}                              // ignore any matches that aren't listed.
@@ -185,12 +345,34 @@ impl State {
     fn dead(&mut self, ctx: &mut BTerm) {
         ctx.cls();
         ctx.print_centered(5, "You are dead!");
-        ctx.print_centered(6, &format!("You earned {} points", self.score));
-        ctx.print_centered(8, "(P) Play again");
-        ctx.print_centered(9, "(Q) Quit Game");
+        ctx.print_centered(6, format!("You earned {} points", self.score));
+
+        // render the ranked high-score table, highlighting this session's
+        // new entry if it qualified for the top ten.
+        ctx.print_centered(8, "High Scores");
+        for (i, entry) in self.scores.entries.iter().enumerate() {
+            let line = format!("{:>2}. {}", i + 1, entry);
+            if Some(i) == self.last_rank {
+                ctx.print_color_centered(10 + i as i32, YELLOW, BLACK, &line);
+            } else {
+                ctx.print_centered(10 + i as i32, &line);
+            }
+        }
+
+        ctx.print_centered(22, "(P) Play again");
+        ctx.print_centered(23, "(Q) Quit Game");
+
+        // a one-time offer to drop into Novice mode after a quick death.
+        if self.show_novice {
+            ctx.print_centered(24, "(N) Switch to Novice?");
+        }
 
         if let Some(key) = ctx.key {
             match key {
+                VirtualKeyCode::N if self.show_novice => {
+                    self.difficulty = Difficulty::Novice; // accept the novice-mode offer.
+                    self.show_novice = false;
+                }
                 VirtualKeyCode::P => self.restart(), // if P was pressed, restart the game.
                 VirtualKeyCode::Q => ctx.quitting = true, // if Q was pressed set quitting to true
                 _ => {}                              // ignore any matches that aren't listed.
@@ -215,6 +397,7 @@ impl GameState for State {
         match self.mode {
             GameMode::Menu => self.main_menu(ctx),
             GameMode::Playing => self.play(ctx),
+            GameMode::Paused => self.paused(ctx),
             GameMode::End => self.dead(ctx),
         }
     }
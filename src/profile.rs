@@ -0,0 +1,63 @@
+// Persistent high-score table for flappy dragon.
+//
+// The table is a small JSON file kept in the platform config directory
+// (via the `directories` crate) so progression survives between runs.
+// Every file operation degrades silently — a missing or unreadable file
+// just yields an empty table rather than crashing the game.
+
+use serde::{Deserialize, Serialize};
+
+const MAX_ENTRIES: usize = 10; // the table keeps the top ten scores.
+const FILE_NAME: &str = "scores.json";
+
+// The ranked high-score list, highest first. It holds only the scores;
+// insert() returns the new entry's rank for the caller to highlight.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Scores {
+    pub entries: Vec<i32>,
+}
+impl Scores {
+    // Load the table from the config directory, or start empty if it
+    // isn't there yet or can't be read/parsed.
+    pub fn load() -> Self {
+        match Self::path() {
+            Some(path) => std::fs::read_to_string(path)
+                .ok()
+                .and_then(|text| serde_json::from_str(&text).ok())
+                .unwrap_or_default(),
+            None => Scores::default(),
+        }
+    }
+    // Does `score` earn a place in the top ten?
+    pub fn qualifies(&self, score: i32) -> bool {
+        self.entries.len() < MAX_ENTRIES || self.entries.iter().any(|&e| score > e)
+    }
+    // Insert `score` in descending order, trim to the top ten, and return
+    // the index it landed at so the caller can highlight it.
+    pub fn insert(&mut self, score: i32) -> usize {
+        let rank = self
+            .entries
+            .iter()
+            .position(|&e| score > e)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, score);
+        self.entries.truncate(MAX_ENTRIES);
+        rank
+    }
+    // Write the table back to disk, creating the config directory if
+    // needed. Errors are ignored so a read-only disk never crashes.
+    pub fn save(&self) {
+        if let Some(path) = Self::path() {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(text) = serde_json::to_string_pretty(self) {
+                let _ = std::fs::write(path, text);
+            }
+        }
+    }
+    fn path() -> Option<std::path::PathBuf> {
+        directories::ProjectDirs::from("", "Rosalita", "flappy-dragon")
+            .map(|dirs| dirs.config_dir().join(FILE_NAME))
+    }
+}